@@ -0,0 +1,143 @@
+use crate::interner::Interner;
+use crate::parser::{Expr, LiteralValue, Stmt};
+use crate::tokens::TokenType;
+
+/// Pretty-prints statements and expressions as fully-parenthesized
+/// S-expressions, e.g. `(* (- 1) (group 2))`. Mostly useful for inspecting
+/// what the parser produced while debugging the scanner/parser.
+pub struct AstPrinter<'a> {
+    interner: &'a Interner,
+}
+
+impl<'a> AstPrinter<'a> {
+    pub fn new(interner: &'a Interner) -> Self {
+        Self { interner }
+    }
+
+    pub fn print(&self, statements: &[Stmt]) -> String {
+        statements
+            .iter()
+            .map(|stmt| self.print_stmt(stmt))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn print_stmt(&self, stmt: &Stmt) -> String {
+        match stmt {
+            Stmt::Expression(expr) => self.print_expr(expr),
+            Stmt::Print(expr) => self.parenthesize("print", &[expr]),
+            Stmt::Var { name, initializer } => {
+                let name = self.interner.resolve(*name);
+                match initializer {
+                    Some(expr) => format!("(var {} {})", name, self.print_expr(expr)),
+                    None => format!("(var {})", name),
+                }
+            }
+            Stmt::Block(statements) => {
+                let body = statements
+                    .iter()
+                    .map(|stmt| self.print_stmt(stmt))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                format!("(block {})", body)
+            }
+        }
+    }
+
+    fn print_expr(&self, expr: &Expr) -> String {
+        match expr {
+            Expr::Assign { name, value } => {
+                format!(
+                    "(= {} {})",
+                    self.interner.resolve(*name),
+                    self.print_expr(value)
+                )
+            }
+            Expr::Logical {
+                left,
+                operator,
+                right,
+            } => self.parenthesize(&operator_lexeme(operator), &[left, right]),
+            Expr::Binary {
+                left,
+                operator,
+                right,
+            } => self.parenthesize(&operator_lexeme(operator), &[left, right]),
+            Expr::Unary { operator, right } => {
+                self.parenthesize(&operator_lexeme(operator), &[right])
+            }
+            Expr::Literal(literal) => self.print_literal(literal),
+            Expr::Grouping(inner) => self.parenthesize("group", &[inner]),
+            Expr::Variable(name) => self.interner.resolve(*name).to_string(),
+        }
+    }
+
+    fn print_literal(&self, literal: &LiteralValue) -> String {
+        match literal {
+            LiteralValue::Number(n) => n.to_string(),
+            LiteralValue::Str(s) => self.interner.resolve(*s).to_string(),
+            LiteralValue::Bool(b) => b.to_string(),
+            LiteralValue::Nil => "nil".to_string(),
+        }
+    }
+
+    fn parenthesize(&self, name: &str, exprs: &[&Expr]) -> String {
+        let mut out = format!("({}", name);
+        for expr in exprs {
+            out.push(' ');
+            out.push_str(&self.print_expr(expr));
+        }
+        out.push(')');
+        out
+    }
+}
+
+fn operator_lexeme(operator: &TokenType) -> String {
+    use TokenType::*;
+
+    match operator {
+        Plus => "+",
+        Minus => "-",
+        Star => "*",
+        Slash => "/",
+        Bang => "!",
+        BangEqual => "!=",
+        Equal => "=",
+        EqualEqual => "==",
+        Greater => ">",
+        GreaterEqual => ">=",
+        Less => "<",
+        LessEqual => "<=",
+        And => "and",
+        Or => "or",
+        other => return format!("{:?}", other),
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+fn print(source: &str) -> String {
+    use crate::parser::Parser;
+    use crate::scanner::Scanner;
+
+    let (tokens, interner) = Scanner::new(source).scan_tokens().unwrap();
+    let statements = Parser::new(tokens, source).parse().unwrap();
+    AstPrinter::new(&interner).print(&statements)
+}
+
+#[test]
+fn test_parenthesizes_unary_and_grouping() {
+    assert_eq!(print("-1 * (2);"), "(* (- 1) (group 2))");
+}
+
+#[test]
+fn test_parenthesizes_logical_with_lowercase_lexeme() {
+    assert_eq!(print("1 and 2;"), "(and 1 2)");
+    assert_eq!(print("1 or 2;"), "(or 1 2)");
+}
+
+#[test]
+fn test_parenthesizes_var_and_block_statements() {
+    assert_eq!(print("var a = 1;"), "(var a 1)");
+    assert_eq!(print("{ print 1; }"), "(block (print 1))");
+}
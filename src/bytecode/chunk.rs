@@ -0,0 +1,76 @@
+use super::opcode::OpCode;
+use super::value::Value;
+
+/// A compiled sequence of opcodes, its constant pool, and a parallel table of
+/// source lines (one entry per byte in `code`) used for error reporting.
+#[derive(Default)]
+pub struct Chunk {
+    code: Vec<u8>,
+    constants: Vec<Value>,
+    lines: Vec<usize>,
+}
+
+impl Chunk {
+    pub fn new() -> Self {
+        Self {
+            code: vec![],
+            constants: vec![],
+            lines: vec![],
+        }
+    }
+
+    pub fn write(&mut self, byte: u8, line: usize) {
+        self.code.push(byte);
+        self.lines.push(line);
+    }
+
+    pub fn write_op(&mut self, op: OpCode, line: usize) {
+        self.write(op as u8, line);
+    }
+
+    /// Adds a value to the constant pool and returns its index, or `None` if
+    /// the pool is full: indices are a single byte, so a chunk can only ever
+    /// hold 256 constants.
+    pub fn add_constant(&mut self, value: Value) -> Option<u8> {
+        if self.constants.len() > u8::MAX as usize {
+            return None;
+        }
+
+        self.constants.push(value);
+        Some((self.constants.len() - 1) as u8)
+    }
+
+    pub fn code(&self) -> &[u8] {
+        &self.code
+    }
+
+    pub fn constant(&self, index: u8) -> &Value {
+        &self.constants[index as usize]
+    }
+
+    pub fn line(&self, offset: usize) -> usize {
+        self.lines[offset]
+    }
+}
+
+#[test]
+fn test_add_constant_returns_increasing_indices() {
+    let mut chunk = Chunk::new();
+    let a = chunk.add_constant(Value::Number(1.0)).unwrap();
+    let b = chunk.add_constant(Value::Number(2.0)).unwrap();
+    assert_eq!(a, 0);
+    assert_eq!(b, 1);
+    assert_eq!(chunk.constant(a), &Value::Number(1.0));
+    assert_eq!(chunk.constant(b), &Value::Number(2.0));
+}
+
+#[test]
+fn test_add_constant_errors_past_256_entries() {
+    let mut chunk = Chunk::new();
+    for _ in 0..=u8::MAX as usize {
+        assert!(chunk.add_constant(Value::Nil).is_some());
+    }
+    // The 257th constant would need a two-byte index; since indices are a
+    // single byte, this must fail rather than silently wrap around.
+    assert_eq!(chunk.add_constant(Value::Nil), None);
+}
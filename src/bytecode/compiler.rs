@@ -0,0 +1,273 @@
+use crate::errors::{report_error, LoxDiag, LoxResult};
+use crate::interner::Interner;
+use crate::tokens::{Token, TokenType};
+
+use super::chunk::Chunk;
+use super::opcode::OpCode;
+use super::value::Value;
+
+// Binding power of unary `!`/`-`, higher than any binary operator below.
+const UNARY_BP: u8 = 9;
+
+// Same precedence table as the tree-walking parser, minus `and`/`or` (there's
+// no jump opcode to short-circuit them with yet).
+fn infix_binding_power(which: &TokenType) -> Option<(u8, u8)> {
+    use TokenType::*;
+
+    let bp = match which {
+        EqualEqual | BangEqual => 1,
+        Greater | GreaterEqual | Less | LessEqual => 3,
+        Plus | Minus => 5,
+        Star | Slash => 7,
+        _ => return None,
+    };
+
+    Some((bp, bp + 1))
+}
+
+/// A single-pass Pratt compiler: it walks the scanner's tokens once and emits
+/// opcodes directly, with no intermediate AST.
+pub struct Compiler<'source, 'a> {
+    tokens: Vec<Token<'source>>,
+    current: usize,
+    chunk: Chunk,
+    interner: &'a Interner,
+}
+
+impl<'source, 'a> Compiler<'source, 'a> {
+    pub fn new(tokens: Vec<Token<'source>>, interner: &'a Interner) -> Self {
+        Self {
+            tokens,
+            current: 0,
+            chunk: Chunk::new(),
+            interner,
+        }
+    }
+
+    pub fn compile(mut self) -> LoxDiag<Chunk> {
+        let mut errors = vec![];
+
+        while !self.is_at_end() {
+            if let Err(err) = self.declaration() {
+                errors.push(err);
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(self.chunk)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn declaration(&mut self) -> LoxResult<()> {
+        if self.matches_token(&TokenType::Var) {
+            self.var_declaration()
+        } else {
+            self.statement()
+        }
+    }
+
+    fn var_declaration(&mut self) -> LoxResult<()> {
+        let name = match self.advance().which {
+            TokenType::Identifier(name) => self.interner.resolve(name).to_string(),
+            other => {
+                return self.error_result(format!("expected a variable name, got {:?}", other))
+            }
+        };
+        let line = self.previous().line();
+
+        if self.matches_token(&TokenType::Equal) {
+            self.expression()?;
+        } else {
+            self.emit_constant(Value::Nil, line)?;
+        }
+
+        self.consume(TokenType::Semi, "expected ';' after variable declaration")?;
+
+        let name_index = self.add_constant(Value::Str(name))?;
+        self.chunk.write_op(OpCode::DefineGlobal, line);
+        self.chunk.write(name_index, line);
+        Ok(())
+    }
+
+    fn statement(&mut self) -> LoxResult<()> {
+        if self.matches_token(&TokenType::Print) {
+            self.print_statement()
+        } else {
+            self.expression_statement()
+        }
+    }
+
+    fn print_statement(&mut self) -> LoxResult<()> {
+        let line = self.previous().line();
+        self.expression()?;
+        self.consume(TokenType::Semi, "expected ';' after value")?;
+        self.chunk.write_op(OpCode::Print, line);
+        Ok(())
+    }
+
+    fn expression_statement(&mut self) -> LoxResult<()> {
+        let line = self.peek().line();
+        self.expression()?;
+        self.consume(TokenType::Semi, "expected ';' after expression")?;
+        self.chunk.write_op(OpCode::Pop, line);
+        Ok(())
+    }
+
+    fn expression(&mut self) -> LoxResult<()> {
+        self.parse_precedence(0)
+    }
+
+    // The Pratt/precedence-climbing loop: compile a prefix expression, then
+    // keep consuming infix operators whose binding power is at least `min_bp`.
+    fn parse_precedence(&mut self, min_bp: u8) -> LoxResult<()> {
+        self.parse_prefix()?;
+
+        while let Some((left_bp, right_bp)) = infix_binding_power(&self.peek().which) {
+            if left_bp < min_bp {
+                break;
+            }
+
+            let operator = self.advance().which;
+            let line = self.previous().line();
+            self.parse_precedence(right_bp)?;
+            self.emit_binary(&operator, line)?;
+        }
+
+        Ok(())
+    }
+
+    fn parse_prefix(&mut self) -> LoxResult<()> {
+        let which = self.advance().which;
+        let line = self.previous().line();
+
+        match which {
+            TokenType::Number(n) => self.emit_constant(Value::Number(n), line),
+            TokenType::String(s) => {
+                let s = self.interner.resolve(s).to_string();
+                self.emit_constant(Value::Str(s), line)
+            }
+            TokenType::True => self.emit_constant(Value::Bool(true), line),
+            TokenType::False => self.emit_constant(Value::Bool(false), line),
+            TokenType::Nil => self.emit_constant(Value::Nil, line),
+
+            TokenType::Identifier(name) => {
+                let name = self.interner.resolve(name).to_string();
+                let index = self.add_constant(Value::Str(name))?;
+                self.chunk.write_op(OpCode::GetGlobal, line);
+                self.chunk.write(index, line);
+                Ok(())
+            }
+
+            TokenType::LeftParen => {
+                self.parse_precedence(0)?;
+                self.consume(TokenType::RightParen, "expected ')' after expression")?;
+                Ok(())
+            }
+
+            TokenType::Bang => {
+                self.parse_precedence(UNARY_BP)?;
+                self.chunk.write_op(OpCode::Not, line);
+                Ok(())
+            }
+
+            TokenType::Minus => {
+                self.parse_precedence(UNARY_BP)?;
+                self.chunk.write_op(OpCode::Negate, line);
+                Ok(())
+            }
+
+            other => self.error_result(format!("unexpected token {:?}", other)),
+        }
+    }
+
+    // `a != b`, `a >= b` and `a <= b` aren't their own opcodes: they're
+    // synthesized from `Equal`/`Greater`/`Less` plus `Not`.
+    fn emit_binary(&mut self, operator: &TokenType, line: usize) -> LoxResult<()> {
+        match operator {
+            TokenType::Plus => self.chunk.write_op(OpCode::Add, line),
+            TokenType::Minus => self.chunk.write_op(OpCode::Sub, line),
+            TokenType::Star => self.chunk.write_op(OpCode::Mul, line),
+            TokenType::Slash => self.chunk.write_op(OpCode::Div, line),
+
+            TokenType::EqualEqual => self.chunk.write_op(OpCode::Equal, line),
+            TokenType::BangEqual => {
+                self.chunk.write_op(OpCode::Equal, line);
+                self.chunk.write_op(OpCode::Not, line);
+            }
+
+            TokenType::Greater => self.chunk.write_op(OpCode::Greater, line),
+            TokenType::GreaterEqual => {
+                self.chunk.write_op(OpCode::Less, line);
+                self.chunk.write_op(OpCode::Not, line);
+            }
+
+            TokenType::Less => self.chunk.write_op(OpCode::Less, line),
+            TokenType::LessEqual => {
+                self.chunk.write_op(OpCode::Greater, line);
+                self.chunk.write_op(OpCode::Not, line);
+            }
+
+            other => return self.error_result(format!("unexpected binary operator {:?}", other)),
+        }
+        Ok(())
+    }
+
+    fn emit_constant(&mut self, value: Value, line: usize) -> LoxResult<()> {
+        let index = self.add_constant(value)?;
+        self.chunk.write_op(OpCode::Constant, line);
+        self.chunk.write(index, line);
+        Ok(())
+    }
+
+    /// Adds a value to the chunk's constant pool, reporting a compile error
+    /// if the pool has overflowed its single-byte index space.
+    fn add_constant(&mut self, value: Value) -> LoxResult<u8> {
+        match self.chunk.add_constant(value) {
+            Some(index) => Ok(index),
+            None => self.error_result("too many constants in one chunk".to_string()),
+        }
+    }
+
+    fn consume(&mut self, expected: TokenType, msg: &str) -> LoxResult<()> {
+        if self.peek().which == expected {
+            self.advance();
+            Ok(())
+        } else {
+            self.error_result(msg.to_string())
+        }
+    }
+
+    fn matches_token(&mut self, expected: &TokenType) -> bool {
+        if &self.peek().which == expected {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn advance(&mut self) -> &Token<'source> {
+        if !self.is_at_end() {
+            self.current += 1;
+        }
+        self.previous()
+    }
+
+    fn previous(&self) -> &Token<'source> {
+        &self.tokens[self.current - 1]
+    }
+
+    fn peek(&self) -> &Token<'source> {
+        &self.tokens[self.current]
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.peek().which == TokenType::EOF
+    }
+
+    fn error_result<T>(&self, msg: String) -> LoxResult<T> {
+        report_error(self.peek().line(), "compiling".to_string(), msg)
+    }
+}
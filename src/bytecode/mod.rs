@@ -0,0 +1,8 @@
+pub mod chunk;
+pub mod compiler;
+pub mod opcode;
+pub mod value;
+pub mod vm;
+
+pub use compiler::Compiler;
+pub use vm::Vm;
@@ -0,0 +1,68 @@
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(u8)]
+pub enum OpCode {
+    Constant,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Negate,
+    Not,
+    Equal,
+    Greater,
+    Less,
+    Print,
+    Pop,
+    DefineGlobal,
+    GetGlobal,
+    Return,
+}
+
+impl OpCode {
+    pub fn from_u8(byte: u8) -> Option<OpCode> {
+        let op = match byte {
+            0 => OpCode::Constant,
+            1 => OpCode::Add,
+            2 => OpCode::Sub,
+            3 => OpCode::Mul,
+            4 => OpCode::Div,
+            5 => OpCode::Negate,
+            6 => OpCode::Not,
+            7 => OpCode::Equal,
+            8 => OpCode::Greater,
+            9 => OpCode::Less,
+            10 => OpCode::Print,
+            11 => OpCode::Pop,
+            12 => OpCode::DefineGlobal,
+            13 => OpCode::GetGlobal,
+            14 => OpCode::Return,
+            _ => return None,
+        };
+        Some(op)
+    }
+}
+
+#[test]
+fn test_from_u8_round_trips_every_opcode() {
+    let opcodes = [
+        OpCode::Constant,
+        OpCode::Add,
+        OpCode::Sub,
+        OpCode::Mul,
+        OpCode::Div,
+        OpCode::Negate,
+        OpCode::Not,
+        OpCode::Equal,
+        OpCode::Greater,
+        OpCode::Less,
+        OpCode::Print,
+        OpCode::Pop,
+        OpCode::DefineGlobal,
+        OpCode::GetGlobal,
+        OpCode::Return,
+    ];
+    for op in opcodes {
+        assert_eq!(OpCode::from_u8(op as u8), Some(op));
+    }
+    assert_eq!(OpCode::from_u8(255), None);
+}
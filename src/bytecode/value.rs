@@ -0,0 +1,26 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Number(f64),
+    Str(String),
+    Bool(bool),
+    Nil,
+}
+
+impl Value {
+    pub fn is_truthy(&self) -> bool {
+        !matches!(self, Value::Nil | Value::Bool(false))
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Number(n) => write!(formatter, "{}", n),
+            Value::Str(s) => write!(formatter, "{}", s),
+            Value::Bool(b) => write!(formatter, "{}", b),
+            Value::Nil => write!(formatter, "nil"),
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+
+use crate::errors::{report_error, LoxError, LoxResult};
+
+use super::chunk::Chunk;
+use super::opcode::OpCode;
+use super::value::Value;
+
+/// A stack-based bytecode interpreter.
+pub struct Vm {
+    globals: HashMap<String, Value>,
+}
+
+impl Vm {
+    pub fn new() -> Self {
+        Self {
+            globals: HashMap::new(),
+        }
+    }
+
+    pub fn run(&mut self, chunk: &Chunk) -> LoxResult<()> {
+        let mut stack: Vec<Value> = vec![];
+        let mut ip = 0;
+
+        while ip < chunk.code().len() {
+            let line = chunk.line(ip);
+            let op = OpCode::from_u8(chunk.code()[ip])
+                .unwrap_or_else(|| panic!("invalid opcode at offset {}", ip));
+            ip += 1;
+
+            match op {
+                OpCode::Constant => {
+                    let index = chunk.code()[ip];
+                    ip += 1;
+                    stack.push(chunk.constant(index).clone());
+                }
+
+                OpCode::Add => {
+                    let b = pop(&mut stack, line)?;
+                    let a = pop(&mut stack, line)?;
+                    let result = match (a, b) {
+                        (Value::Number(a), Value::Number(b)) => Value::Number(a + b),
+                        (Value::Str(a), Value::Str(b)) => Value::Str(a + &b),
+                        _ => {
+                            return Err(runtime_error(
+                                line,
+                                "operands must be two numbers or two strings".to_string(),
+                            ))
+                        }
+                    };
+                    stack.push(result);
+                }
+
+                OpCode::Sub => numeric_binary_op(&mut stack, line, |a, b| Value::Number(a - b))?,
+                OpCode::Mul => numeric_binary_op(&mut stack, line, |a, b| Value::Number(a * b))?,
+                OpCode::Div => numeric_binary_op(&mut stack, line, |a, b| Value::Number(a / b))?,
+                OpCode::Greater => {
+                    numeric_binary_op(&mut stack, line, |a, b| Value::Bool(a > b))?
+                }
+                OpCode::Less => numeric_binary_op(&mut stack, line, |a, b| Value::Bool(a < b))?,
+
+                OpCode::Equal => {
+                    let b = pop(&mut stack, line)?;
+                    let a = pop(&mut stack, line)?;
+                    stack.push(Value::Bool(a == b));
+                }
+
+                OpCode::Negate => {
+                    let value = pop(&mut stack, line)?;
+                    match value {
+                        Value::Number(n) => stack.push(Value::Number(-n)),
+                        _ => return Err(runtime_error(line, "operand must be a number".to_string())),
+                    }
+                }
+
+                OpCode::Not => {
+                    let value = pop(&mut stack, line)?;
+                    stack.push(Value::Bool(!value.is_truthy()));
+                }
+
+                OpCode::Print => {
+                    let value = pop(&mut stack, line)?;
+                    println!("{}", value);
+                }
+
+                OpCode::Pop => {
+                    pop(&mut stack, line)?;
+                }
+
+                OpCode::DefineGlobal => {
+                    let name = self.read_global_name(chunk, &mut ip);
+                    let value = pop(&mut stack, line)?;
+                    self.globals.insert(name, value);
+                }
+
+                OpCode::GetGlobal => {
+                    let name = self.read_global_name(chunk, &mut ip);
+                    let value = self.globals.get(&name).cloned().ok_or_else(|| {
+                        runtime_error(line, format!("undefined variable '{}'", name))
+                    })?;
+                    stack.push(value);
+                }
+
+                OpCode::Return => return Ok(()),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_global_name(&self, chunk: &Chunk, ip: &mut usize) -> String {
+        let index = chunk.code()[*ip];
+        *ip += 1;
+        match chunk.constant(index) {
+            Value::Str(name) => name.clone(),
+            other => panic!("global name constant is not a string: {:?}", other),
+        }
+    }
+}
+
+fn pop(stack: &mut Vec<Value>, line: usize) -> LoxResult<Value> {
+    stack
+        .pop()
+        .ok_or_else(|| runtime_error(line, "stack underflow".to_string()))
+}
+
+fn numeric_binary_op(
+    stack: &mut Vec<Value>,
+    line: usize,
+    f: impl Fn(f64, f64) -> Value,
+) -> LoxResult<()> {
+    let b = pop(stack, line)?;
+    let a = pop(stack, line)?;
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => {
+            stack.push(f(a, b));
+            Ok(())
+        }
+        _ => Err(runtime_error(line, "operands must be numbers".to_string())),
+    }
+}
+
+fn runtime_error(line: usize, msg: String) -> LoxError {
+    match report_error::<()>(line, "at runtime".to_string(), msg) {
+        Ok(_) => unreachable!(),
+        Err(err) => err,
+    }
+}
+
+#[cfg(test)]
+fn run(source: &str) -> LoxResult<Vm> {
+    use crate::scanner::Scanner;
+    use super::compiler::Compiler;
+
+    let (tokens, interner) = Scanner::new(source).scan_tokens().unwrap();
+    let chunk = Compiler::new(tokens, &interner).compile().unwrap();
+    let mut vm = Vm::new();
+    vm.run(&chunk)?;
+    Ok(vm)
+}
+
+#[test]
+fn test_arithmetic_and_globals() {
+    let vm = run("var a = 1 + 2 * 3; var b = \"foo\" + \"bar\";").unwrap();
+    assert_eq!(vm.globals.get("a"), Some(&Value::Number(7.0)));
+    assert_eq!(vm.globals.get("b"), Some(&Value::Str("foobar".to_string())));
+}
+
+#[test]
+fn test_comparisons_and_synthesized_operators() {
+    // `!=`, `>=` and `<=` aren't opcodes of their own; this exercises the
+    // `Equal`/`Greater`/`Less` + `Not` synthesis in `emit_binary`.
+    let vm = run(
+        r#"
+        var a = 1 != 2;
+        var b = 2 >= 2;
+        var c = 3 <= 2;
+        "#,
+    )
+    .unwrap();
+    assert_eq!(vm.globals.get("a"), Some(&Value::Bool(true)));
+    assert_eq!(vm.globals.get("b"), Some(&Value::Bool(true)));
+    assert_eq!(vm.globals.get("c"), Some(&Value::Bool(false)));
+}
+
+#[test]
+fn test_undefined_global_errors() {
+    assert!(run("print missing;").is_err());
+}
+
+#[test]
+fn test_stack_underflow_errors_instead_of_panicking() {
+    let mut chunk = Chunk::default();
+    chunk.write_op(OpCode::Add, 1);
+    let mut vm = Vm::new();
+    assert!(vm.run(&chunk).is_err());
+}
@@ -3,16 +3,40 @@ use std::fmt;
 use std::io;
 use std::ops::Deref;
 
+/// The source-line text and column needed to render a caret-underlined
+/// snippet under an error message.
+#[derive(Debug)]
+pub struct Snippet {
+    pub line_text: String,
+    pub column: usize,
+    pub len: usize,
+}
+
 #[derive(Debug)]
 pub struct OwnError {
     line: usize,
     context: String,
     msg: String,
+    snippet: Option<Snippet>,
 }
 
 impl OwnError {
     fn new(line: usize, context: String, msg: String) -> Self {
-        Self { line, context, msg }
+        Self {
+            line,
+            context,
+            msg,
+            snippet: None,
+        }
+    }
+
+    fn with_snippet(line: usize, snippet: Snippet, context: String, msg: String) -> Self {
+        Self {
+            line,
+            context,
+            msg,
+            snippet: Some(snippet),
+        }
     }
 }
 
@@ -22,7 +46,17 @@ impl fmt::Display for OwnError {
             formatter,
             "[{}] Error {}: {}",
             self.line, self.context, &self.msg
-        )
+        )?;
+
+        if let Some(snippet) = &self.snippet {
+            let column = snippet.column.max(1);
+            let remaining = snippet.line_text.len().saturating_sub(column - 1).max(1);
+            let len = snippet.len.max(1).min(remaining);
+            writeln!(formatter, "    {}", snippet.line_text)?;
+            writeln!(formatter, "    {}{}", " ".repeat(column - 1), "^".repeat(len))?;
+        }
+
+        Ok(())
     }
 }
 
@@ -93,3 +127,26 @@ pub fn report_error<T>(
     };
     Err(LoxError::Own(OwnError::new(line, context, msg)))
 }
+
+/// Like [`report_error`], but attaches a [`Snippet`] so the rendered error
+/// underlines the offending column in its source line.
+pub fn report_error_at<T>(
+    line: usize,
+    snippet: Snippet,
+    context: String,
+    msg: impl Into<DisplayableError>,
+) -> LoxResult<T> {
+    let msg = match msg.into() {
+        DisplayableError::s(s) => s,
+        DisplayableError::errors(errors) => errors
+            .iter()
+            .map(|err| match err {
+                LoxError::Own(own) => format!("{}", own),
+                LoxError::Other(other) => format!("{}", other),
+            })
+            .fold("".to_string(), |acc, x| format!("{}\n{}", acc, x)),
+    };
+    Err(LoxError::Own(OwnError::with_snippet(
+        line, snippet, context, msg,
+    )))
+}
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+/// A cheap, `Copy` handle to a string deduplicated by an [`Interner`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Symbol(u32);
+
+/// Deduplicates strings into a backing arena, handing out a [`Symbol`] that
+/// compares and hashes as cheaply as an integer. Equal strings always get the
+/// same `Symbol`, so comparing identifier and string tokens is an integer
+/// comparison. The tree-walking interpreter and the bytecode VM resolve
+/// symbols back to `String`s to key their variable tables, so variable
+/// lookups still hash and compare strings.
+#[derive(Default)]
+pub struct Interner {
+    strings: Vec<String>,
+    symbols: HashMap<String, Symbol>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intern(&mut self, s: &str) -> Symbol {
+        if let Some(&symbol) = self.symbols.get(s) {
+            return symbol;
+        }
+
+        let symbol = Symbol(self.strings.len() as u32);
+        self.strings.push(s.to_string());
+        self.symbols.insert(s.to_string(), symbol);
+        symbol
+    }
+
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.strings[symbol.0 as usize]
+    }
+}
+
+#[test]
+fn test_intern_dedups_equal_strings() {
+    let mut interner = Interner::new();
+    let a = interner.intern("hello");
+    let b = interner.intern("hello");
+    let c = interner.intern("world");
+
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert_eq!(interner.resolve(a), "hello");
+    assert_eq!(interner.resolve(c), "world");
+}
@@ -0,0 +1,323 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::errors::{report_error, LoxError, LoxResult};
+use crate::interner::Interner;
+use crate::parser::{Expr, LiteralValue, Stmt};
+use crate::tokens::TokenType;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Number(f64),
+    Str(String),
+    Bool(bool),
+    Nil,
+}
+
+impl Value {
+    fn is_truthy(&self) -> bool {
+        !matches!(self, Value::Nil | Value::Bool(false))
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Number(n) => write!(formatter, "{}", n),
+            Value::Str(s) => write!(formatter, "{}", s),
+            Value::Bool(b) => write!(formatter, "{}", b),
+            Value::Nil => write!(formatter, "nil"),
+        }
+    }
+}
+
+pub struct Environment {
+    values: HashMap<String, Value>,
+    enclosing: Option<Rc<RefCell<Environment>>>,
+}
+
+impl Environment {
+    pub fn new() -> Self {
+        Self {
+            values: HashMap::new(),
+            enclosing: None,
+        }
+    }
+
+    pub fn with_enclosing(enclosing: Rc<RefCell<Environment>>) -> Self {
+        Self {
+            values: HashMap::new(),
+            enclosing: Some(enclosing),
+        }
+    }
+
+    pub fn define(&mut self, name: String, value: Value) {
+        self.values.insert(name, value);
+    }
+
+    pub fn get(&self, name: &str) -> Option<Value> {
+        if let Some(value) = self.values.get(name) {
+            Some(value.clone())
+        } else {
+            self.enclosing
+                .as_ref()
+                .and_then(|enclosing| enclosing.borrow().get(name))
+        }
+    }
+
+    pub fn assign(&mut self, name: &str, value: Value) -> bool {
+        if self.values.contains_key(name) {
+            self.values.insert(name.to_string(), value);
+            true
+        } else if let Some(enclosing) = &self.enclosing {
+            enclosing.borrow_mut().assign(name, value)
+        } else {
+            false
+        }
+    }
+}
+
+pub struct Interpreter {
+    environment: Rc<RefCell<Environment>>,
+    interner: Interner,
+}
+
+impl Interpreter {
+    pub fn new(interner: Interner) -> Self {
+        Self {
+            environment: Rc::new(RefCell::new(Environment::new())),
+            interner,
+        }
+    }
+
+    pub fn interpret(&mut self, statements: &[Stmt]) -> LoxResult<()> {
+        for stmt in statements {
+            self.eval_stmt(stmt)?;
+        }
+        Ok(())
+    }
+
+    fn eval_stmt(&mut self, stmt: &Stmt) -> LoxResult<()> {
+        match stmt {
+            Stmt::Expression(expr) => {
+                self.eval_expr(expr)?;
+                Ok(())
+            }
+
+            Stmt::Print(expr) => {
+                let value = self.eval_expr(expr)?;
+                println!("{}", value);
+                Ok(())
+            }
+
+            Stmt::Var { name, initializer } => {
+                let value = match initializer {
+                    Some(expr) => self.eval_expr(expr)?,
+                    None => Value::Nil,
+                };
+                let name = self.interner.resolve(*name).to_string();
+                self.environment.borrow_mut().define(name, value);
+                Ok(())
+            }
+
+            Stmt::Block(statements) => {
+                let previous = self.environment.clone();
+                self.environment = Rc::new(RefCell::new(Environment::with_enclosing(previous.clone())));
+                let result = self.interpret(statements);
+                self.environment = previous;
+                result
+            }
+        }
+    }
+
+    fn eval_expr(&mut self, expr: &Expr) -> LoxResult<Value> {
+        match expr {
+            Expr::Literal(literal) => Ok(self.eval_literal(literal)),
+
+            Expr::Grouping(inner) => self.eval_expr(inner),
+
+            Expr::Variable(name) => {
+                let name = self.interner.resolve(*name);
+                self.environment
+                    .borrow()
+                    .get(name)
+                    .ok_or_else(|| runtime_error(format!("undefined variable '{}'", name)))
+            }
+
+            Expr::Assign { name, value } => {
+                let value = self.eval_expr(value)?;
+                let name = self.interner.resolve(*name);
+                if self.environment.borrow_mut().assign(name, value.clone()) {
+                    Ok(value)
+                } else {
+                    Err(runtime_error(format!("undefined variable '{}'", name)))
+                }
+            }
+
+            Expr::Logical {
+                left,
+                operator,
+                right,
+            } => {
+                let left = self.eval_expr(left)?;
+                match operator {
+                    TokenType::Or if left.is_truthy() => Ok(left),
+                    TokenType::And if !left.is_truthy() => Ok(left),
+                    _ => self.eval_expr(right),
+                }
+            }
+
+            Expr::Unary { operator, right } => {
+                let right = self.eval_expr(right)?;
+                match operator {
+                    TokenType::Minus => match right {
+                        Value::Number(n) => Ok(Value::Number(-n)),
+                        _ => Err(runtime_error("operand must be a number".to_string())),
+                    },
+                    TokenType::Bang => Ok(Value::Bool(!right.is_truthy())),
+                    _ => unreachable!("unexpected unary operator {:?}", operator),
+                }
+            }
+
+            Expr::Binary {
+                left,
+                operator,
+                right,
+            } => {
+                let left = self.eval_expr(left)?;
+                let right = self.eval_expr(right)?;
+                eval_binary(operator, left, right)
+            }
+        }
+    }
+
+    fn eval_literal(&self, literal: &LiteralValue) -> Value {
+        match literal {
+            LiteralValue::Number(n) => Value::Number(*n),
+            LiteralValue::Str(s) => Value::Str(self.interner.resolve(*s).to_string()),
+            LiteralValue::Bool(b) => Value::Bool(*b),
+            LiteralValue::Nil => Value::Nil,
+        }
+    }
+}
+
+fn eval_binary(operator: &TokenType, left: Value, right: Value) -> LoxResult<Value> {
+    match operator {
+        TokenType::Plus => match (left, right) {
+            (Value::Number(a), Value::Number(b)) => Ok(Value::Number(a + b)),
+            (Value::Str(a), Value::Str(b)) => Ok(Value::Str(a + &b)),
+            _ => Err(runtime_error(
+                "operands must be two numbers or two strings".to_string(),
+            )),
+        },
+        TokenType::Minus => numeric_op(left, right, |a, b| Value::Number(a - b)),
+        TokenType::Star => numeric_op(left, right, |a, b| Value::Number(a * b)),
+        TokenType::Slash => numeric_op(left, right, |a, b| Value::Number(a / b)),
+        TokenType::Greater => numeric_op(left, right, |a, b| Value::Bool(a > b)),
+        TokenType::GreaterEqual => numeric_op(left, right, |a, b| Value::Bool(a >= b)),
+        TokenType::Less => numeric_op(left, right, |a, b| Value::Bool(a < b)),
+        TokenType::LessEqual => numeric_op(left, right, |a, b| Value::Bool(a <= b)),
+        TokenType::EqualEqual => Ok(Value::Bool(left == right)),
+        TokenType::BangEqual => Ok(Value::Bool(left != right)),
+        _ => unreachable!("unexpected binary operator {:?}", operator),
+    }
+}
+
+fn numeric_op(left: Value, right: Value, f: impl Fn(f64, f64) -> Value) -> LoxResult<Value> {
+    match (left, right) {
+        (Value::Number(a), Value::Number(b)) => Ok(f(a, b)),
+        _ => Err(runtime_error("operands must be numbers".to_string())),
+    }
+}
+
+fn runtime_error(msg: String) -> LoxError {
+    match report_error::<()>(0, "at runtime".to_string(), msg) {
+        Ok(_) => unreachable!(),
+        Err(err) => err,
+    }
+}
+
+#[cfg(test)]
+fn run(source: &str) -> LoxResult<Interpreter> {
+    use crate::parser::Parser;
+    use crate::scanner::Scanner;
+
+    let (tokens, interner) = Scanner::new(source).scan_tokens().unwrap();
+    let statements = Parser::new(tokens, source).parse().unwrap();
+    let mut interpreter = Interpreter::new(interner);
+    interpreter.interpret(&statements)?;
+    Ok(interpreter)
+}
+
+#[test]
+fn test_arithmetic_and_string_concat() {
+    let interp = run("var a = 1 + 2 * 3; var b = \"foo\" + \"bar\";").unwrap();
+    assert_eq!(interp.environment.borrow().get("a"), Some(Value::Number(7.0)));
+    assert_eq!(
+        interp.environment.borrow().get("b"),
+        Some(Value::Str("foobar".to_string()))
+    );
+}
+
+#[test]
+fn test_truthiness_and_short_circuit() {
+    // `and`/`or` yield one of their operands and must short-circuit: the
+    // right-hand side is not evaluated once the left side decides the result,
+    // so the undefined `missing` below is never looked up (looking it up
+    // would be a runtime error).
+    let interp = run(
+        r#"
+        var a = nil and 1;
+        var b = false or 2;
+        var c = 1 and 2;
+        var d = true or missing;
+        var e = false and missing;
+        "#,
+    )
+    .unwrap();
+    assert_eq!(interp.environment.borrow().get("a"), Some(Value::Nil));
+    assert_eq!(interp.environment.borrow().get("b"), Some(Value::Number(2.0)));
+    assert_eq!(interp.environment.borrow().get("c"), Some(Value::Number(2.0)));
+    assert_eq!(interp.environment.borrow().get("d"), Some(Value::Bool(true)));
+    assert_eq!(interp.environment.borrow().get("e"), Some(Value::Bool(false)));
+}
+
+#[test]
+fn test_block_scoping() {
+    let interp = run(
+        r#"
+        var a = "outer";
+        {
+            var a = "inner";
+        }
+        "#,
+    )
+    .unwrap();
+    // The block's `a` shadows the outer one only within the block; once the
+    // block ends, the outer environment is restored untouched.
+    assert_eq!(
+        interp.environment.borrow().get("a"),
+        Some(Value::Str("outer".to_string()))
+    );
+}
+
+#[test]
+fn test_block_assignment_mutates_enclosing_scope() {
+    let interp = run(
+        r#"
+        var a = 1;
+        {
+            a = 2;
+        }
+        "#,
+    )
+    .unwrap();
+    assert_eq!(interp.environment.borrow().get("a"), Some(Value::Number(2.0)));
+}
+
+#[test]
+fn test_undefined_variable_errors() {
+    assert!(run("print missing;").is_err());
+}
@@ -1,42 +1,93 @@
+#[macro_use]
+extern crate lazy_static;
+
 use std::env;
 use std::fs::File;
 use std::io;
 use std::io::prelude::*;
 use std::process;
 
+mod ast_printer;
+mod bytecode;
 mod errors;
+mod interner;
+mod interpreter;
+mod parser;
 mod scanner;
 mod tokens;
 
-use errors::LoxResult;
+use ast_printer::AstPrinter;
+use bytecode::{Compiler, Vm};
+use errors::{report_error, LoxResult};
+use interpreter::Interpreter;
+use parser::Parser;
 use scanner::Scanner;
 
-fn run(script: &str) -> LoxResult {
-    println!("Running: {}", script);
+#[derive(Clone, Copy)]
+enum Backend {
+    TreeWalk,
+    Bytecode,
+}
 
+/// What to do with a script: run it through a backend, or dump an
+/// intermediate representation instead of executing anything.
+#[derive(Clone, Copy)]
+enum Mode {
+    Execute(Backend),
+    DumpTokens,
+    DumpAst,
+}
+
+fn run(script: &str, mode: Mode) -> LoxResult<()> {
     let scanner = Scanner::new(script);
-    let tokens = scanner.scan_tokens();
+    let (tokens, interner) = scanner
+        .scan_tokens()
+        .or_else(|errors| report_error(0, "scanning".to_string(), errors))?;
+
+    match mode {
+        Mode::DumpTokens => {
+            for token in &tokens {
+                println!("{}", token);
+            }
+            Ok(())
+        }
 
-    for token in tokens {
-        println!("{}", token);
-    }
+        Mode::DumpAst => {
+            let statements = Parser::new(tokens, script)
+                .parse()
+                .or_else(|errors| report_error(0, "parsing".to_string(), errors))?;
 
-    Ok(())
-}
+            println!("{}", AstPrinter::new(&interner).print(&statements));
+            Ok(())
+        }
 
-fn run_file(path: &str) -> LoxResult {
-    println!("Running file {}", path);
+        Mode::Execute(Backend::TreeWalk) => {
+            let statements = Parser::new(tokens, script)
+                .parse()
+                .or_else(|errors| report_error(0, "parsing".to_string(), errors))?;
+
+            Interpreter::new(interner).interpret(&statements)
+        }
 
+        Mode::Execute(Backend::Bytecode) => {
+            let chunk = Compiler::new(tokens, &interner)
+                .compile()
+                .or_else(|errors| report_error(0, "compiling".to_string(), errors))?;
+
+            Vm::new().run(&chunk)
+        }
+    }
+}
+
+fn run_file(path: &str, mode: Mode) -> LoxResult<()> {
     let mut file = File::open(path)?;
     let mut script_content = String::new();
     file.read_to_string(&mut script_content)?;
 
-    run(&script_content)
+    run(&script_content, mode)
 }
 
-fn run_prompt() -> LoxResult {
-    println!("Running prompt.");
-
+fn run_prompt(mode: Mode) -> LoxResult<()> {
     let stdout = io::stdout();
     let mut stdout = stdout.lock();
 
@@ -54,19 +105,32 @@ fn run_prompt() -> LoxResult {
             return Ok(());
         }
 
-        run(&buffer)?;
+        run(&buffer, mode)?;
         buffer.clear();
     }
 }
 
 fn main() {
-    let args: Vec<_> = env::args().skip(1).collect();
+    let mut backend = Backend::TreeWalk;
+    let mut dump = None;
+    let mut positional = vec![];
+
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "-b" | "--bytecode" => backend = Backend::Bytecode,
+            "-t" | "--tokens" => dump = Some(Mode::DumpTokens),
+            "-a" | "--ast" => dump = Some(Mode::DumpAst),
+            _ => positional.push(arg),
+        }
+    }
+
+    let mode = dump.unwrap_or(Mode::Execute(backend));
 
-    let result = match args.len() {
-        0 => run_prompt(),
-        1 => run_file(args[0].as_str()),
+    let result = match positional.len() {
+        0 => run_prompt(mode),
+        1 => run_file(positional[0].as_str(), mode),
         _ => {
-            eprintln!("Usage: lox [script]");
+            eprintln!("Usage: lox [-b|--bytecode] [-t|--tokens] [-a|--ast] [script]");
             process::exit(64);
         }
     };
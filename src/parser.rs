@@ -0,0 +1,452 @@
+use crate::errors::{report_error, report_error_at, LoxDiag, LoxResult, Snippet};
+use crate::interner::Symbol;
+use crate::tokens::{Token, TokenType};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LiteralValue {
+    Number(f64),
+    Str(Symbol),
+    Bool(bool),
+    Nil,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Assign {
+        name: Symbol,
+        value: Box<Expr>,
+    },
+    Logical {
+        left: Box<Expr>,
+        operator: TokenType,
+        right: Box<Expr>,
+    },
+    Binary {
+        left: Box<Expr>,
+        operator: TokenType,
+        right: Box<Expr>,
+    },
+    Unary {
+        operator: TokenType,
+        right: Box<Expr>,
+    },
+    Literal(LiteralValue),
+    Grouping(Box<Expr>),
+    Variable(Symbol),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Stmt {
+    Expression(Expr),
+    Print(Expr),
+    Var {
+        name: Symbol,
+        initializer: Option<Expr>,
+    },
+    Block(Vec<Stmt>),
+}
+
+// Binding power of unary `!`/`-`, higher than any binary operator below.
+const UNARY_BP: u8 = 13;
+
+// Binding power of each infix operator, lowest to highest: `or`, then `and`,
+// then equality, then comparisons, then `+`/`-`, then `*`/`/`. All of them
+// are left-associative, so the right-hand side is parsed with `bp + 1`.
+fn infix_binding_power(which: &TokenType) -> Option<(u8, u8)> {
+    use TokenType::*;
+
+    let bp = match which {
+        Or => 1,
+        And => 3,
+        EqualEqual | BangEqual => 5,
+        Greater | GreaterEqual | Less | LessEqual => 7,
+        Plus | Minus => 9,
+        Star | Slash => 11,
+        _ => return None,
+    };
+
+    Some((bp, bp + 1))
+}
+
+pub struct Parser<'source> {
+    tokens: Vec<Token<'source>>,
+    source: &'source str,
+    current: usize,
+}
+
+impl<'source> Parser<'source> {
+    pub fn new(tokens: Vec<Token<'source>>, source: &'source str) -> Self {
+        Self {
+            tokens,
+            source,
+            current: 0,
+        }
+    }
+
+    /// Builds a caret-underline [`Snippet`] for the token's source span,
+    /// pulling out the full text of the line it falls on. Mirrors
+    /// `Scanner::snippet`.
+    fn snippet(&self, token: &Token<'source>) -> Snippet {
+        let (start, end) = token.span();
+        let line_start = self.source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = self.source[start..]
+            .find('\n')
+            .map_or(self.source.len(), |i| start + i);
+
+        Snippet {
+            line_text: self.source[line_start..line_end].to_string(),
+            column: start - line_start + 1,
+            len: end.saturating_sub(start).max(1),
+        }
+    }
+
+    pub fn parse(mut self) -> LoxDiag<Vec<Stmt>> {
+        let mut statements = vec![];
+        let mut errors = vec![];
+
+        while !self.is_at_end() {
+            match self.declaration() {
+                Ok(stmt) => statements.push(stmt),
+                Err(err) => {
+                    errors.push(err);
+                    self.synchronize();
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(statements)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn declaration(&mut self) -> LoxResult<Stmt> {
+        if self.matches_token(&TokenType::Var) {
+            self.var_declaration()
+        } else {
+            self.statement()
+        }
+    }
+
+    fn var_declaration(&mut self) -> LoxResult<Stmt> {
+        let name = match self.advance().which {
+            TokenType::Identifier(name) => name,
+            other => {
+                return self.error_result(
+                    "parsing a variable declaration",
+                    format!("expected a variable name, got {:?}", other),
+                )
+            }
+        };
+
+        let initializer = if self.matches_token(&TokenType::Equal) {
+            Some(self.expression()?)
+        } else {
+            None
+        };
+
+        self.consume(TokenType::Semi, "expected ';' after variable declaration")?;
+        Ok(Stmt::Var { name, initializer })
+    }
+
+    fn statement(&mut self) -> LoxResult<Stmt> {
+        if self.matches_token(&TokenType::Print) {
+            self.print_statement()
+        } else if self.matches_token(&TokenType::LeftBrace) {
+            Ok(Stmt::Block(self.block()?))
+        } else {
+            self.expression_statement()
+        }
+    }
+
+    fn print_statement(&mut self) -> LoxResult<Stmt> {
+        let value = self.expression()?;
+        self.consume(TokenType::Semi, "expected ';' after value")?;
+        Ok(Stmt::Print(value))
+    }
+
+    fn expression_statement(&mut self) -> LoxResult<Stmt> {
+        let expr = self.expression()?;
+        self.consume(TokenType::Semi, "expected ';' after expression")?;
+        Ok(Stmt::Expression(expr))
+    }
+
+    fn block(&mut self) -> LoxResult<Vec<Stmt>> {
+        let mut statements = vec![];
+
+        while self.peek().which != TokenType::RightBrace && !self.is_at_end() {
+            statements.push(self.declaration()?);
+        }
+
+        self.consume(TokenType::RightBrace, "expected '}' after block")?;
+        Ok(statements)
+    }
+
+    fn expression(&mut self) -> LoxResult<Expr> {
+        self.assignment()
+    }
+
+    // Assignment is right-associative and binds loosest of all, so it sits
+    // above the precedence-climbing loop rather than inside it.
+    fn assignment(&mut self) -> LoxResult<Expr> {
+        let expr = self.parse_precedence(0)?;
+
+        if self.matches_token(&TokenType::Equal) {
+            let equals_line = self.tokens[self.current - 1].line();
+            let value = self.assignment()?;
+
+            return match expr {
+                Expr::Variable(name) => Ok(Expr::Assign {
+                    name,
+                    value: Box::new(value),
+                }),
+                _ => report_error(
+                    equals_line,
+                    "parsing an assignment".to_string(),
+                    "invalid assignment target".to_string(),
+                ),
+            };
+        }
+
+        Ok(expr)
+    }
+
+    // The Pratt/precedence-climbing loop: parse a prefix expression, then keep
+    // consuming infix operators whose binding power is at least `min_bp`.
+    fn parse_precedence(&mut self, min_bp: u8) -> LoxResult<Expr> {
+        let mut left = self.parse_prefix()?;
+
+        while let Some((left_bp, right_bp)) = infix_binding_power(&self.peek().which) {
+            if left_bp < min_bp {
+                break;
+            }
+
+            let operator = self.advance().which;
+            let right = self.parse_precedence(right_bp)?;
+            left = match operator {
+                TokenType::And | TokenType::Or => Expr::Logical {
+                    left: Box::new(left),
+                    operator,
+                    right: Box::new(right),
+                },
+                _ => Expr::Binary {
+                    left: Box::new(left),
+                    operator,
+                    right: Box::new(right),
+                },
+            };
+        }
+
+        Ok(left)
+    }
+
+    fn parse_prefix(&mut self) -> LoxResult<Expr> {
+        let which = self.advance().which;
+
+        match which {
+            TokenType::Number(n) => Ok(Expr::Literal(LiteralValue::Number(n))),
+            TokenType::String(s) => Ok(Expr::Literal(LiteralValue::Str(s))),
+            TokenType::True => Ok(Expr::Literal(LiteralValue::Bool(true))),
+            TokenType::False => Ok(Expr::Literal(LiteralValue::Bool(false))),
+            TokenType::Nil => Ok(Expr::Literal(LiteralValue::Nil)),
+            TokenType::Identifier(name) => Ok(Expr::Variable(name)),
+
+            TokenType::LeftParen => {
+                let expr = self.parse_precedence(0)?;
+                self.consume(TokenType::RightParen, "expected ')' after expression")?;
+                Ok(Expr::Grouping(Box::new(expr)))
+            }
+
+            TokenType::Bang => {
+                let right = self.parse_precedence(UNARY_BP)?;
+                Ok(Expr::Unary {
+                    operator: TokenType::Bang,
+                    right: Box::new(right),
+                })
+            }
+
+            TokenType::Minus => {
+                let right = self.parse_precedence(UNARY_BP)?;
+                Ok(Expr::Unary {
+                    operator: TokenType::Minus,
+                    right: Box::new(right),
+                })
+            }
+
+            other => self.error_result("parsing", format!("unexpected token {:?}", other)),
+        }
+    }
+
+    fn consume(&mut self, expected: TokenType, msg: &str) -> LoxResult<()> {
+        if self.peek().which == expected {
+            self.advance();
+            Ok(())
+        } else {
+            self.error_result("parsing", msg.to_string())
+        }
+    }
+
+    fn matches_token(&mut self, expected: &TokenType) -> bool {
+        if &self.peek().which == expected {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn advance(&mut self) -> &Token<'source> {
+        if !self.is_at_end() {
+            self.current += 1;
+        }
+        &self.tokens[self.current - 1]
+    }
+
+    fn peek(&self) -> &Token<'source> {
+        &self.tokens[self.current]
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.peek().which == TokenType::EOF
+    }
+
+    // Skip to the next statement boundary after a parse error, so a single
+    // mistake doesn't hide every error after it.
+    fn synchronize(&mut self) {
+        while !self.is_at_end() {
+            if self.advance().which == TokenType::Semi {
+                return;
+            }
+
+            match self.peek().which {
+                TokenType::Class
+                | TokenType::Fun
+                | TokenType::Var
+                | TokenType::For
+                | TokenType::If
+                | TokenType::While
+                | TokenType::Print
+                | TokenType::Return => return,
+                _ => {}
+            }
+        }
+    }
+
+    fn error_result<T>(&self, context: &str, msg: String) -> LoxResult<T> {
+        let token = self.peek();
+        let msg = format!("{} (near '{}')", msg, token.lexem());
+        report_error_at(token.line(), self.snippet(token), context.to_string(), msg)
+    }
+}
+
+#[cfg(test)]
+fn parse(source: &str) -> Vec<Stmt> {
+    use crate::scanner::Scanner;
+
+    let (tokens, _interner) = Scanner::new(source).scan_tokens().unwrap();
+    Parser::new(tokens, source).parse().unwrap()
+}
+
+#[test]
+fn test_precedence_and_associativity() {
+    let statements = parse("1 + 2 * 3 - -4;");
+    assert_eq!(statements.len(), 1);
+
+    match &statements[0] {
+        Stmt::Expression(Expr::Binary {
+            left,
+            operator: TokenType::Minus,
+            right,
+        }) => {
+            assert!(matches!(
+                **left,
+                Expr::Binary {
+                    operator: TokenType::Plus,
+                    ..
+                }
+            ));
+            assert!(matches!(
+                **right,
+                Expr::Unary {
+                    operator: TokenType::Minus,
+                    ..
+                }
+            ));
+        }
+        other => panic!("expected a top-level '-' binary expression, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_comparison_binds_tighter_than_equality() {
+    // Comparisons should bind tighter than `==`, matching
+    // `infix_binding_power`.
+    let statements = parse("1 < 2 == 3 < 4;");
+    assert_eq!(statements.len(), 1);
+    match &statements[0] {
+        Stmt::Expression(Expr::Binary {
+            operator: TokenType::EqualEqual,
+            left,
+            right,
+        }) => {
+            assert!(matches!(
+                **left,
+                Expr::Binary {
+                    operator: TokenType::Less,
+                    ..
+                }
+            ));
+            assert!(matches!(
+                **right,
+                Expr::Binary {
+                    operator: TokenType::Less,
+                    ..
+                }
+            ));
+        }
+        other => panic!("expected a top-level '==' binary expression, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_grouping_and_assignment() {
+    let statements = parse("var a; a = (1 + 2);");
+    assert_eq!(statements.len(), 2);
+    assert!(matches!(statements[0], Stmt::Var { .. }));
+
+    match &statements[1] {
+        Stmt::Expression(Expr::Assign { value, .. }) => {
+            assert!(matches!(**value, Expr::Grouping(_)));
+        }
+        other => panic!("expected an assignment expression statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_block_and_print_statements() {
+    let statements = parse("{ var a = 1; print a; }");
+    assert_eq!(statements.len(), 1);
+
+    match &statements[0] {
+        Stmt::Block(inner) => {
+            assert_eq!(inner.len(), 2);
+            assert!(matches!(inner[0], Stmt::Var { .. }));
+            assert!(matches!(inner[1], Stmt::Print(_)));
+        }
+        other => panic!("expected a block statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_errors_accumulate() {
+    use crate::scanner::Scanner;
+
+    let source = "var 1 = 2; var 3 = 4;";
+    let (tokens, _interner) = Scanner::new(source).scan_tokens().unwrap();
+    let errors = Parser::new(tokens, source).parse().unwrap_err();
+
+    // Both malformed `var` declarations should be reported, rather than the
+    // parser bailing out after the first error.
+    assert_eq!(errors.len(), 2);
+}
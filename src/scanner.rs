@@ -1,4 +1,5 @@
-use crate::errors::{report_error, LoxDiag, LoxResult};
+use crate::errors::{report_error_at, LoxDiag, LoxResult, Snippet};
+use crate::interner::Interner;
 use crate::tokens::{Token, TokenType};
 
 use std::collections::HashMap;
@@ -7,7 +8,7 @@ use std::str;
 use std::str::CharIndices;
 
 lazy_static! {
-    static ref KEYWORDS: HashMap<&'static str, TokenType<'static>> = {
+    static ref KEYWORDS: HashMap<&'static str, TokenType> = {
         let mut m = HashMap::new();
         m.insert("and", TokenType::And);
         m.insert("class", TokenType::Class);
@@ -36,6 +37,7 @@ pub struct Scanner<'source> {
     current: usize,
     line: usize,
     tokens: Vec<Token<'source>>,
+    interner: Interner,
 }
 
 impl<'source> Scanner<'source> {
@@ -47,10 +49,11 @@ impl<'source> Scanner<'source> {
             current: 0,
             line: 1,
             tokens: vec![],
+            interner: Interner::new(),
         }
     }
 
-    pub fn scan_tokens(mut self) -> LoxDiag<Vec<Token<'source>>> {
+    pub fn scan_tokens(mut self) -> LoxDiag<(Vec<Token<'source>>, Interner)> {
         let mut errors = vec![];
 
         loop {
@@ -66,21 +69,44 @@ impl<'source> Scanner<'source> {
             }
         }
 
-        self.tokens.push(Token::new(TokenType::EOF, "", self.line));
+        self.tokens.push(Token::new(
+            TokenType::EOF,
+            "",
+            (self.current, self.current),
+            self.line,
+        ));
         if errors.len() == 0 {
-            Ok(self.tokens)
+            Ok((self.tokens, self.interner))
         } else {
             Err(errors)
         }
     }
 
-    fn add_token(&mut self, which: TokenType<'source>) {
-        self.tokens.push(Token::new(which, "", self.line));
+    fn add_token(&mut self, which: TokenType) {
+        let span = (self.start, self.current + 1);
+        let lexem = &self.source[span.0..span.1];
+        self.tokens.push(Token::new(which, lexem, span, self.line));
+    }
+
+    /// Builds a caret-underline [`Snippet`] for the source range `[start,
+    /// end)`, pulling out the full text of the line `start` falls on.
+    fn snippet(&self, start: usize, end: usize) -> Snippet {
+        let line_start = self.source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = self.source[start..]
+            .find('\n')
+            .map_or(self.source.len(), |i| start + i);
+
+        Snippet {
+            line_text: self.source[line_start..line_end].to_string(),
+            column: start - line_start + 1,
+            len: end.saturating_sub(start).max(1),
+        }
     }
 
     fn matches(&mut self, expected: char) -> bool {
-        if let Some(observed) = self.peek() {
-            observed == expected
+        if self.peek() == Some(expected) {
+            self.advance().unwrap();
+            true
         } else {
             false
         }
@@ -124,55 +150,163 @@ impl<'source> Scanner<'source> {
             if c == '"' {
                 let substr =
                     str::from_utf8(&self.source.as_bytes()[self.start + 1..self.current]).unwrap();
-                self.add_token(TokenType::String(substr));
+                let symbol = self.interner.intern(substr);
+                self.add_token(TokenType::String(symbol));
                 return Ok(());
             }
         }
-        report_error(
+        report_error_at(
             self.line,
+            self.snippet(self.start, self.current + 1),
             "when reading a string".to_string(),
             "unterminated string".to_string(),
         )
     }
 
+    // `/*` has already been consumed; nested `/* ... */` pairs increase and
+    // decrease `depth`, so the comment only ends once the outermost one does.
+    fn scan_block_comment(&mut self) -> LoxResult<()> {
+        let mut depth = 1;
+
+        while depth > 0 {
+            match self.advance() {
+                None => {
+                    return report_error_at(
+                        self.line,
+                        self.snippet(self.start, self.current + 1),
+                        "when parsing a block comment".to_string(),
+                        "unterminated block comment".to_string(),
+                    );
+                }
+                Some('\n') => self.line += 1,
+                Some('*') if self.peek() == Some('/') => {
+                    self.advance().unwrap();
+                    depth -= 1;
+                }
+                Some('/') if self.peek() == Some('*') => {
+                    self.advance().unwrap();
+                    depth += 1;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
     fn scan_number(&mut self, first_digit: char) -> LoxResult<()> {
-        let mut number = first_digit.to_digit(10).unwrap() as f64;
-        let mut fractional_power_of_ten: Option<f64> = None;
+        if first_digit == '0' && matches!(self.peek(), Some('x') | Some('X')) {
+            self.advance().unwrap();
+            return self.scan_hex_number();
+        }
 
-        while let Some(c) = self.peek() {
-            if is_digit(c) {
+        self.consume_digits();
+
+        if self.peek() == Some('.') && matches!(self.peek_next(), Some(d) if is_digit(d)) {
+            self.advance().unwrap(); // consume the '.'
+            self.consume_digits();
+        }
+
+        if matches!(self.peek(), Some('e') | Some('E')) && self.exponent_follows() {
+            self.advance().unwrap(); // consume 'e'/'E'
+            if matches!(self.peek(), Some('+') | Some('-')) {
                 self.advance().unwrap();
-                let c_num = c.to_digit(10).unwrap() as f64;
-                if let Some(decimal) = fractional_power_of_ten.as_mut() {
-                    number += c_num * *decimal;
-                    *decimal /= 10.0;
-                } else {
-                    number *= 10.0;
-                    number += c_num;
-                }
-            } else if c == '.' {
-                if let Some(d) = self.peek_next() {
-                    if is_digit(d) {
-                        self.advance().unwrap();
-                        if fractional_power_of_ten.is_some() {
-                            return report_error(
-                                self.line,
-                                "when parsing a number".to_string(),
-                                "unexpected dot".to_string(),
-                            )?;
-                        }
-                        fractional_power_of_ten = Some(0.1);
-                        continue;
-                    }
-                }
+            }
+            self.consume_digits();
+        }
+
+        let lexem = &self.source[self.start..self.current + 1];
+
+        match lexem.parse() {
+            Ok(number) => {
+                self.add_token(TokenType::Number(number));
+                Ok(())
+            }
+            Err(_) => report_error_at(
+                self.line,
+                self.snippet(self.start, self.current + 1),
+                "when parsing a number".to_string(),
+                format!("invalid number literal '{}'", lexem),
+            ),
+        }
+    }
+
+    fn scan_hex_number(&mut self) -> LoxResult<()> {
+        self.consume_hex_digits();
+
+        let mut has_fraction = false;
+        if self.peek() == Some('.') && matches!(self.peek_next(), Some(d) if d.is_ascii_hexdigit()) {
+            has_fraction = true;
+            self.advance().unwrap(); // consume the '.'
+            self.consume_hex_digits();
+        }
+
+        let mut has_exponent = false;
+        if matches!(self.peek(), Some('p') | Some('P')) {
+            has_exponent = true;
+            self.advance().unwrap(); // consume 'p'/'P'
+            if matches!(self.peek(), Some('+') | Some('-')) {
+                self.advance().unwrap();
+            }
+            self.consume_digits();
+        }
+
+        let lexem = &self.source[self.start..self.current + 1];
+
+        let number = if has_fraction || has_exponent {
+            parse_hex_float(lexem)
+        } else {
+            u64::from_str_radix(&lexem[2..], 16)
+                .ok()
+                .map(|n| n as f64)
+        };
+
+        match number {
+            Some(number) => {
+                self.add_token(TokenType::Number(number));
+                Ok(())
+            }
+            None => report_error_at(
+                self.line,
+                self.snippet(self.start, self.current + 1),
+                "when parsing a number".to_string(),
+                format!("invalid hex number literal '{}'", lexem),
+            ),
+        }
+    }
+
+    fn consume_digits(&mut self) {
+        while let Some(c) = self.peek() {
+            if !is_digit(c) {
                 break;
-            } else {
+            }
+            self.advance().unwrap();
+        }
+    }
+
+    fn consume_hex_digits(&mut self) {
+        while let Some(c) = self.peek() {
+            if !c.is_ascii_hexdigit() {
                 break;
             }
+            self.advance().unwrap();
         }
+    }
 
-        self.add_token(TokenType::Number(number));
-        Ok(())
+    /// True if the current position is an `e`/`E` followed by an optional
+    /// sign and at least one digit, without consuming anything.
+    fn exponent_follows(&self) -> bool {
+        let mut lookahead = self.source_iter.clone();
+        match lookahead.next() {
+            Some((_, 'e')) | Some((_, 'E')) => {}
+            _ => return false,
+        }
+
+        if let Some((_, '+')) | Some((_, '-')) = lookahead.peek() {
+            lookahead.next();
+        }
+
+        matches!(lookahead.peek(), Some((_, c)) if is_digit(*c))
     }
 
     fn scan_identifier(&mut self) -> LoxResult<()> {
@@ -186,9 +320,10 @@ impl<'source> Scanner<'source> {
         let substr = str::from_utf8(&self.source.as_bytes()[self.start..self.current + 1]).unwrap();
 
         if let Some(token) = KEYWORDS.get(substr) {
-            self.add_token((*token).clone());
+            self.add_token(*token);
         } else {
-            self.add_token(TokenType::Identifier(substr));
+            let symbol = self.interner.intern(substr);
+            self.add_token(TokenType::Identifier(symbol));
         }
         Ok(())
     }
@@ -288,6 +423,8 @@ impl<'source> Scanner<'source> {
                             break;
                         }
                     }
+                } else if self.matches('*') {
+                    self.scan_block_comment()?;
                 } else {
                     self.add_token(TokenType::Slash);
                 }
@@ -312,8 +449,9 @@ impl<'source> Scanner<'source> {
             '\n' => self.line += 1,
 
             _ => {
-                report_error(
+                report_error_at(
                     self.line,
+                    self.snippet(self.start, self.current + 1),
                     "parsing".to_string(),
                     format!("unexpected character '{}'", c),
                 )?;
@@ -336,10 +474,40 @@ fn is_alpha_numeric(c: char) -> bool {
     is_digit(c) || is_alpha(c)
 }
 
+/// Decodes a hex float literal such as `0x1.8p3` (hex integer and fractional
+/// mantissa, optional base-2 exponent after `p`/`P`) into its `f64` value.
+fn parse_hex_float(lexem: &str) -> Option<f64> {
+    let rest = lexem.strip_prefix("0x").or_else(|| lexem.strip_prefix("0X"))?;
+
+    let (mantissa, exponent) = match rest.find(|c| c == 'p' || c == 'P') {
+        Some(i) => (&rest[..i], rest[i + 1..].parse::<i32>().ok()?),
+        None => (rest, 0),
+    };
+
+    let (int_part, frac_part) = match mantissa.find('.') {
+        Some(i) => (&mantissa[..i], &mantissa[i + 1..]),
+        None => (mantissa, ""),
+    };
+
+    let mut value = if int_part.is_empty() {
+        0.0
+    } else {
+        u64::from_str_radix(int_part, 16).ok()? as f64
+    };
+
+    let mut scale = 1.0 / 16.0;
+    for c in frac_part.chars() {
+        value += c.to_digit(16)? as f64 * scale;
+        scale /= 16.0;
+    }
+
+    Some(value * 2f64.powi(exponent))
+}
+
 #[test]
 fn test_empty() {
     let s = Scanner::new("");
-    let tokens = s.scan_tokens().unwrap();
+    let (tokens, _interner) = s.scan_tokens().unwrap();
     assert_eq!(tokens.len(), 1);
     assert_eq!(tokens[0].which, TokenType::EOF);
 }
@@ -347,7 +515,7 @@ fn test_empty() {
 #[test]
 fn test_comments() {
     let s = Scanner::new("// hello world");
-    let tokens = s.scan_tokens().unwrap();
+    let (tokens, _interner) = s.scan_tokens().unwrap();
     assert_eq!(tokens.len(), 1);
     assert_eq!(tokens[0].which, TokenType::EOF);
 }
@@ -360,7 +528,7 @@ fn test_empty_multilines() {
     "#,
     );
 
-    let tokens = s.scan_tokens().unwrap();
+    let (tokens, _interner) = s.scan_tokens().unwrap();
     assert_eq!(tokens.len(), 1);
     assert_eq!(tokens[0].which, TokenType::EOF);
 }
@@ -374,7 +542,7 @@ fn test_multines() {
 "#,
     );
 
-    let tokens = s.scan_tokens().unwrap();
+    let (tokens, _interner) = s.scan_tokens().unwrap();
     assert_eq!(tokens.len(), 6);
     assert_eq!(tokens[0].which, TokenType::LeftBrace);
     assert_eq!(tokens[1].which, TokenType::RightBrace);
@@ -391,9 +559,12 @@ fn test_scan_string() {
     "terminated 42 string"
 "#,
     );
-    let tokens = s.scan_tokens().unwrap();
+    let (tokens, interner) = s.scan_tokens().unwrap();
     assert_eq!(tokens.len(), 2);
-    assert_eq!(tokens[0].which, TokenType::String("terminated 42 string"));
+    match tokens[0].which {
+        TokenType::String(symbol) => assert_eq!(interner.resolve(symbol), "terminated 42 string"),
+        other => panic!("expected a string token, got {:?}", other),
+    }
     assert_eq!(tokens[1].which, TokenType::EOF);
 
     let s = Scanner::new(
@@ -407,27 +578,99 @@ fn test_scan_string() {
 #[test]
 fn test_scan_number() {
     let s = Scanner::new("423298");
-    let tokens = s.scan_tokens().unwrap();
+    let (tokens, _interner) = s.scan_tokens().unwrap();
     assert_eq!(tokens.len(), 2);
     assert_eq!(tokens[0].which, TokenType::Number(423298.0));
     assert_eq!(tokens[1].which, TokenType::EOF);
 
     let s = Scanner::new("423298.0");
-    let tokens = s.scan_tokens().unwrap();
+    let (tokens, _interner) = s.scan_tokens().unwrap();
     assert_eq!(tokens.len(), 2);
     assert_eq!(tokens[0].which, TokenType::Number(423298.0));
     assert_eq!(tokens[1].which, TokenType::EOF);
 
     let s = Scanner::new("423298.");
-    let tokens = s.scan_tokens().unwrap();
+    let (tokens, _interner) = s.scan_tokens().unwrap();
     assert_eq!(tokens.len(), 3);
     assert_eq!(tokens[0].which, TokenType::Number(423298.0));
     assert_eq!(tokens[1].which, TokenType::Dot);
     assert_eq!(tokens[2].which, TokenType::EOF);
 
     let s = Scanner::new(" 12.34  ");
-    let tokens = s.scan_tokens().unwrap();
+    let (tokens, _interner) = s.scan_tokens().unwrap();
     assert_eq!(tokens.len(), 2);
     assert_eq!(tokens[0].which, TokenType::Number(12.34));
     assert_eq!(tokens[1].which, TokenType::EOF);
 }
+
+#[test]
+fn test_scan_number_scientific_notation() {
+    let s = Scanner::new("1e10");
+    let (tokens, _interner) = s.scan_tokens().unwrap();
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[0].which, TokenType::Number(1e10));
+    assert_eq!(tokens[1].which, TokenType::EOF);
+
+    let s = Scanner::new("6.022e+23");
+    let (tokens, _interner) = s.scan_tokens().unwrap();
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[0].which, TokenType::Number(6.022e+23));
+    assert_eq!(tokens[1].which, TokenType::EOF);
+
+    let s = Scanner::new("1e-3");
+    let (tokens, _interner) = s.scan_tokens().unwrap();
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[0].which, TokenType::Number(1e-3));
+    assert_eq!(tokens[1].which, TokenType::EOF);
+}
+
+#[test]
+fn test_scan_number_hex() {
+    let s = Scanner::new("0x1F");
+    let (tokens, _interner) = s.scan_tokens().unwrap();
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[0].which, TokenType::Number(31.0));
+    assert_eq!(tokens[1].which, TokenType::EOF);
+
+    let s = Scanner::new("0x1.8p3");
+    let (tokens, _interner) = s.scan_tokens().unwrap();
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[0].which, TokenType::Number(12.0));
+    assert_eq!(tokens[1].which, TokenType::EOF);
+}
+
+#[test]
+fn test_block_comments() {
+    let s = Scanner::new("/* a single line comment */ 1");
+    let (tokens, _interner) = s.scan_tokens().unwrap();
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[0].which, TokenType::Number(1.0));
+    assert_eq!(tokens[1].which, TokenType::EOF);
+
+    let s = Scanner::new("/* a /* nested */ comment */ 1");
+    let (tokens, _interner) = s.scan_tokens().unwrap();
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[0].which, TokenType::Number(1.0));
+    assert_eq!(tokens[1].which, TokenType::EOF);
+
+    let s = Scanner::new("/* spans\nmultiple\nlines */ 1");
+    let (tokens, _interner) = s.scan_tokens().unwrap();
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[0].which, TokenType::Number(1.0));
+    assert_eq!(tokens[0].line(), 3);
+
+    let s = Scanner::new("/* unterminated");
+    assert!(s.scan_tokens().is_err());
+}
+
+#[test]
+fn test_two_char_operators_consume_both_chars() {
+    let s = Scanner::new("a != b == c <= d >= e");
+    let (tokens, _interner) = s.scan_tokens().unwrap();
+    assert!(matches!(tokens[1].which, TokenType::BangEqual));
+    assert!(matches!(tokens[3].which, TokenType::EqualEqual));
+    assert!(matches!(tokens[5].which, TokenType::LessEqual));
+    assert!(matches!(tokens[7].which, TokenType::GreaterEqual));
+    assert_eq!(tokens.len(), 10);
+    assert_eq!(tokens[9].which, TokenType::EOF);
+}
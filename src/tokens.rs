@@ -1,7 +1,9 @@
 use std::fmt;
 
-#[derive(Clone, Debug, PartialEq)]
-pub enum TokenType<'source> {
+use crate::interner::Symbol;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TokenType {
     // Single char.
     LeftParen,
     RightParen,
@@ -26,8 +28,8 @@ pub enum TokenType<'source> {
     LessEqual,
 
     // Literals.
-    Identifier(&'source str),
-    String(&'source str),
+    Identifier(Symbol),
+    String(Symbol),
     Number(f64),
 
     // Keywords.
@@ -52,14 +54,39 @@ pub enum TokenType<'source> {
 }
 
 pub struct Token<'source> {
-    pub which: TokenType<'source>, // TODO public?
+    pub which: TokenType, // TODO public?
     lexem: &'source str,
+    span: (usize, usize),
     line: usize,
 }
 
 impl<'source> Token<'source> {
-    pub fn new(which: TokenType<'source>, lexem: &'source str, line: usize) -> Token<'source> {
-        Token { which, lexem, line }
+    pub fn new(
+        which: TokenType,
+        lexem: &'source str,
+        span: (usize, usize),
+        line: usize,
+    ) -> Token<'source> {
+        Token {
+            which,
+            lexem,
+            span,
+            line,
+        }
+    }
+
+    pub fn lexem(&self) -> &str {
+        self.lexem
+    }
+
+    /// Byte offsets into the source this token was scanned from, as `(start,
+    /// end)` with `end` exclusive.
+    pub fn span(&self) -> (usize, usize) {
+        self.span
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
     }
 }
 